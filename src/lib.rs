@@ -1,18 +1,38 @@
 use std::error::Error;
 use std::fmt;
+use std::time::Duration;
 
 use reqwest;
 use serde::{Deserialize, Serialize};
 use serde_json as json;
 
 /// Creating a custom error for mapping Errors to return result from the library handles
-/// The possible errors so far are BadJSONData and HTTPRequestError
+/// The possible errors so far are BadJSONData, HTTPRequestError, InvalidWebhookURL, Timeout,
+/// ServerRejected and FilesAPINotConfigured
 /// BadJSONData maps to a serde_json::error::Error
 /// HTTPRequestError maps to a reqwest::Error
+/// InvalidWebhookURL maps to a reqwest::Error raised while building the request, e.g. an
+/// unparsable webhook URL
+/// Timeout maps to a reqwest::Error raised when a request or connect timeout elapses
+/// ServerRejected carries the status and body text of a non-2xx response from the webhook or
+/// the files API
+///
+/// `#[non_exhaustive]` so new variants can be added without breaking downstream `match`es.
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum MMRSError {
     BadJSONData(serde_json::error::Error),
     HTTPRequestError(reqwest::Error),
+    InvalidWebhookURL(reqwest::Error),
+    Timeout(reqwest::Error),
+    ServerRejected {
+        status: reqwest::StatusCode,
+        body: String,
+    },
+    /// Returned by [`MMClient::upload_file`] when the client wasn't built
+    /// with [`MMClientBuilder::api_base`] and [`MMClientBuilder::token`],
+    /// both of which the files API needs but the webhook sender doesn't.
+    FilesAPINotConfigured,
 }
 
 impl fmt::Display for MMRSError {
@@ -20,11 +40,113 @@ impl fmt::Display for MMRSError {
         match self {
             MMRSError::BadJSONData(e) => write!(f, "Error writing to JSON string: {}", e),
             MMRSError::HTTPRequestError(e) => write!(f, "Error while sending HTTP POST: {}", e),
+            MMRSError::InvalidWebhookURL(e) => write!(f, "Invalid webhook URL: {}", e),
+            MMRSError::Timeout(e) => write!(f, "Request to Mattermost timed out: {}", e),
+            MMRSError::ServerRejected { status, body } => {
+                write!(f, "Mattermost rejected the request ({}): {}", status, body)
+            }
+            MMRSError::FilesAPINotConfigured => write!(
+                f,
+                "MMClient has no api_base/token configured; set both via MMClient::builder to use upload_file"
+            ),
         }
     }
 }
 
-impl Error for MMRSError {}
+impl Error for MMRSError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            MMRSError::BadJSONData(e) => Some(e),
+            MMRSError::HTTPRequestError(e) => Some(e),
+            MMRSError::InvalidWebhookURL(e) => Some(e),
+            MMRSError::Timeout(e) => Some(e),
+            MMRSError::ServerRejected { .. } => None,
+            MMRSError::FilesAPINotConfigured => None,
+        }
+    }
+}
+
+/// Maps a `reqwest::Error` to the most specific `MMRSError` variant it
+/// represents, so callers matching on `MMRSError` can distinguish a timeout
+/// or a malformed webhook URL from a generic transport failure.
+fn classify_reqwest_error(e: reqwest::Error) -> MMRSError {
+    if e.is_timeout() {
+        MMRSError::Timeout(e)
+    } else if e.is_builder() {
+        MMRSError::InvalidWebhookURL(e)
+    } else {
+        MMRSError::HTTPRequestError(e)
+    }
+}
+
+/// A single field within an [`MMAttachment`], rendered by Mattermost as a
+/// column in the attachment body.
+/// See the [Official MatterMost Developer Documentation](https://developers.mattermost.com/integrate/incoming-webhooks/#attachments) for field semantics.
+#[derive(Serialize, Deserialize)]
+pub struct MMField {
+    pub title: String,
+    pub value: String,
+    pub short: bool,
+}
+
+/// Custom struct modeling a Mattermost message attachment, allowing rich,
+/// structured message formatting instead of hand-rolled JSON.
+/// For a description of these fields see the [Official MatterMost Developer Documentation](https://developers.mattermost.com/integrate/incoming-webhooks/#attachments)
+#[derive(Serialize, Deserialize)]
+pub struct MMAttachment {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fallback: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pretext: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author_link: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author_icon: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title_link: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thumb_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub footer: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub fields: Vec<MMField>,
+}
+
+impl MMAttachment {
+    pub fn new() -> MMAttachment {
+        MMAttachment {
+            fallback: None,
+            color: None,
+            pretext: None,
+            author_name: None,
+            author_link: None,
+            author_icon: None,
+            title: None,
+            title_link: None,
+            text: None,
+            image_url: None,
+            thumb_url: None,
+            footer: None,
+            fields: Vec::new(),
+        }
+    }
+}
+
+impl Default for MMAttachment {
+    fn default() -> Self {
+        MMAttachment::new()
+    }
+}
 
 /// Custom struct to serialize the HTTP POST data into a json objecting using serde_json
 /// For a description of these fields see the [Official MatterMost Developer Documentation](https://developers.mattermost.com/integrate/incoming-webhooks/#parameters)
@@ -40,12 +162,16 @@ pub struct MMBody {
     pub icon_url: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub icon_emoji: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub attachments: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub attachments: Vec<MMAttachment>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub r#type: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub props: Option<String>,
+    /// File IDs returned by [`MMClient::upload_file`], embedding previously
+    /// uploaded files/images into this post.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub file_ids: Vec<String>,
 }
 
 impl MMBody {
@@ -56,9 +182,10 @@ impl MMBody {
             username: None,
             icon_url: None,
             icon_emoji: None,
-            attachments: None,
+            attachments: Vec::new(),
             r#type: None,
             props: None,
+            file_ids: Vec::new(),
         }
     }
     /// This function allows us to convert from the struct to a string of JSON which a web server
@@ -68,19 +195,327 @@ impl MMBody {
     }
 }
 
-/// Main function of the library which asynchronously sends the request and returns the status code
-/// response. Will error out on a reqwest::Error if the send results in a failure 
-#[tokio::main]
-pub async fn send_message(uri: &str, body: String) -> Result<reqwest::StatusCode, MMRSError> {
-    let status_code: reqwest::StatusCode = reqwest::Client::new()
+/// Default `User-Agent` header sent with every request unless overridden via
+/// [`MMClientBuilder::user_agent`].
+const DEFAULT_USER_AGENT: &str = "mmrs";
+
+/// Opt-in policy for retrying a send that fails with a connection error or a
+/// `429`/`5xx` response. A `429` response's `Retry-After` header is honored
+/// when present; otherwise the delay doubles after each attempt, capped at
+/// `max_delay`.
+pub struct RetryConfig {
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl RetryConfig {
+    pub fn new(max_attempts: u32, base_delay: Duration, max_delay: Duration) -> RetryConfig {
+        RetryConfig {
+            max_attempts,
+            base_delay,
+            max_delay,
+        }
+    }
+}
+
+impl Default for RetryConfig {
+    /// 3 attempts, starting at a 500ms delay and doubling up to a 30s cap.
+    fn default() -> RetryConfig {
+        RetryConfig {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+/// The full response to a webhook post: the HTTP status code and the raw
+/// response body text. Kept as a struct (rather than just the status code)
+/// so callers can see Mattermost's error explanations, e.g.
+/// `{"message":"Couldn't find the channel."}`, when a post is rejected.
+#[derive(Debug)]
+pub struct MMResponse {
+    pub status: reqwest::StatusCode,
+    pub body: String,
+}
+
+/// Reads the status and body text off `response`, turning a non-2xx status
+/// into an `Err(MMRSError::ServerRejected)`.
+async fn into_mm_response(response: reqwest::Response) -> Result<MMResponse, MMRSError> {
+    let status = response.status();
+    let body = response.text().await.map_err(classify_reqwest_error)?;
+
+    if status.is_success() {
+        Ok(MMResponse { status, body })
+    } else {
+        Err(MMRSError::ServerRejected { status, body })
+    }
+}
+
+/// A single uploaded file as returned by the Mattermost files API.
+#[derive(Deserialize, Debug)]
+struct MMFileInfo {
+    id: String,
+}
+
+/// Body of a successful `POST /api/v4/files` response.
+/// See the [Official MatterMost Developer Documentation](https://developers.mattermost.com/api/#tag/files/operation/UploadFile).
+#[derive(Deserialize, Debug)]
+struct MMFileUploadResponse {
+    file_infos: Vec<MMFileInfo>,
+}
+
+/// A reusable, pooled client for posting to a single Mattermost incoming webhook,
+/// and optionally for uploading files through the REST files API.
+/// Unlike [`send_message`], which opens a fresh `reqwest::Client` (and HTTP
+/// connection) on every call, `MMClient` builds its `reqwest::Client` once so
+/// repeated sends reuse the same connection pool.
+pub struct MMClient {
+    http: reqwest::Client,
+    upload_http: reqwest::Client,
+    uri: String,
+    retry: Option<RetryConfig>,
+    api_base: Option<String>,
+    token: Option<String>,
+}
+
+impl MMClient {
+    /// Builds an `MMClient` for `uri` using default settings. Use
+    /// [`MMClient::builder`] to customize things like the `User-Agent` header
+    /// or retry behavior.
+    pub fn new(uri: &str) -> MMClient {
+        MMClient::builder(uri).build()
+    }
+
+    /// Starts an [`MMClientBuilder`] for `uri`.
+    pub fn builder(uri: &str) -> MMClientBuilder {
+        MMClientBuilder::new(uri)
+    }
+
+    /// Serializes `body` and POSTs it to this client's webhook URL, returning
+    /// the full [`MMResponse`]. If this client was built with a
+    /// [`RetryConfig`], connection errors and `429`/`5xx` responses are
+    /// retried with exponential backoff before the final attempt's result is
+    /// returned; a final non-2xx response is surfaced as
+    /// `Err(MMRSError::ServerRejected)`.
+    pub async fn send(&self, body: MMBody) -> Result<MMResponse, MMRSError> {
+        let json_body = body.to_json()?;
+
+        let mut attempt = 0;
+        let mut delay = self
+            .retry
+            .as_ref()
+            .map(|retry| retry.base_delay)
+            .unwrap_or_default();
+
+        loop {
+            attempt += 1;
+
+            let result = self
+                .http
+                .post(&self.uri)
+                .body(json_body.clone())
+                .send()
+                .await;
+
+            let retry_after = result.as_ref().ok().and_then(retry_after_delay);
+            let classified = result.map_err(classify_reqwest_error);
+
+            let retries_left = match &self.retry {
+                Some(retry) => attempt < retry.max_attempts,
+                None => false,
+            };
+            let should_retry = retries_left
+                && match &classified {
+                    Ok(response) => {
+                        let status = response.status();
+                        status == reqwest::StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+                    }
+                    // A permanently-invalid webhook URL will never succeed on retry.
+                    Err(MMRSError::InvalidWebhookURL(_)) => false,
+                    Err(_) => true,
+                };
+
+            if !should_retry {
+                return into_mm_response(classified?).await;
+            }
+
+            tokio::time::sleep(retry_after.unwrap_or(delay)).await;
+
+            if let Some(retry) = &self.retry {
+                delay = (delay * 2).min(retry.max_delay);
+            }
+        }
+    }
+
+    /// Uploads `reader`'s bytes to `channel_id` as `filename` via a multipart
+    /// `POST` to the Mattermost files API, returning the uploaded file's IDs
+    /// for embedding in a later [`MMBody::file_ids`] post. Requires this
+    /// client to have been built with [`MMClientBuilder::api_base`] and
+    /// [`MMClientBuilder::token`], since (unlike webhook posts) the files API
+    /// needs an authenticated REST endpoint rather than a webhook URL.
+    pub async fn upload_file(
+        &self,
+        channel_id: &str,
+        filename: &str,
+        reader: impl Into<reqwest::Body>,
+    ) -> Result<Vec<String>, MMRSError> {
+        let api_base = self
+            .api_base
+            .as_deref()
+            .ok_or(MMRSError::FilesAPINotConfigured)?;
+        let token = self
+            .token
+            .as_deref()
+            .ok_or(MMRSError::FilesAPINotConfigured)?;
+
+        let part = reqwest::multipart::Part::stream(reader.into()).file_name(filename.to_string());
+        let form = reqwest::multipart::Form::new()
+            .text("channel_id", channel_id.to_string())
+            .part("files", part);
+
+        let url = format!("{}/api/v4/files", api_base.trim_end_matches('/'));
+
+        let response = self
+            .upload_http
+            .post(&url)
+            .bearer_auth(token)
+            .multipart(form)
+            .send()
+            .await
+            .map_err(classify_reqwest_error)?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.map_err(classify_reqwest_error)?;
+            return Err(MMRSError::ServerRejected { status, body });
+        }
+
+        let parsed: MMFileUploadResponse = response.json().await.map_err(classify_reqwest_error)?;
+        Ok(parsed.file_infos.into_iter().map(|info| info.id).collect())
+    }
+}
+
+/// Reads the `Retry-After` header (in seconds) off `response`, if present. The
+/// status is not checked, so a retried `5xx` response's header is honored too.
+fn retry_after_delay(response: &reqwest::Response) -> Option<Duration> {
+    response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok())
+        .map(Duration::from_secs)
+}
+
+/// Builder for [`MMClient`], allowing the `User-Agent` header, retry policy,
+/// and other client-wide settings to be configured before the underlying
+/// `reqwest::Client` is constructed.
+pub struct MMClientBuilder {
+    uri: String,
+    user_agent: String,
+    retry: Option<RetryConfig>,
+    api_base: Option<String>,
+    token: Option<String>,
+}
+
+impl MMClientBuilder {
+    pub fn new(uri: &str) -> MMClientBuilder {
+        MMClientBuilder {
+            uri: uri.to_string(),
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            retry: None,
+            api_base: None,
+            token: None,
+        }
+    }
+
+    /// Overrides the `User-Agent` header sent with every request.
+    pub fn user_agent(mut self, user_agent: &str) -> MMClientBuilder {
+        self.user_agent = user_agent.to_string();
+        self
+    }
+
+    /// Opts the client into retrying transient failures per `retry`. Off by
+    /// default: a client built without calling this never retries.
+    pub fn retry(mut self, retry: RetryConfig) -> MMClientBuilder {
+        self.retry = Some(retry);
+        self
+    }
+
+    /// Sets the Mattermost server's REST API base URL (e.g.
+    /// `https://mattermost.example.com`), used by [`MMClient::upload_file`].
+    /// Unused by [`MMClient::send`], which only talks to the webhook URL.
+    pub fn api_base(mut self, api_base: &str) -> MMClientBuilder {
+        self.api_base = Some(api_base.to_string());
+        self
+    }
+
+    /// Sets the personal access or bot token sent as a bearer token by
+    /// [`MMClient::upload_file`]. Unused by [`MMClient::send`], since
+    /// incoming webhooks don't require authentication.
+    pub fn token(mut self, token: &str) -> MMClientBuilder {
+        self.token = Some(token.to_string());
+        self
+    }
+
+    /// Builds the `MMClient`, constructing its underlying `reqwest::Client`
+    /// with the `Content-Type: application/json` and `User-Agent` headers set
+    /// once for every subsequent request. A second, header-free client is
+    /// built alongside it for [`MMClient::upload_file`], since multipart
+    /// requests set their own `Content-Type` with a boundary.
+    pub fn build(self) -> MMClient {
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert(
+            reqwest::header::CONTENT_TYPE,
+            reqwest::header::HeaderValue::from_static("application/json"),
+        );
+
+        let http = reqwest::Client::builder()
+            .default_headers(headers)
+            .user_agent(self.user_agent.clone())
+            .build()
+            .expect("failed to build reqwest client");
+
+        let upload_http = reqwest::Client::builder()
+            .user_agent(self.user_agent)
+            .build()
+            .expect("failed to build reqwest client");
+
+        MMClient {
+            http,
+            upload_http,
+            uri: self.uri,
+            retry: self.retry,
+            api_base: self.api_base,
+            token: self.token,
+        }
+    }
+}
+
+/// Main function of the library which asynchronously sends the request and returns the full
+/// response. Will error out on a reqwest::Error if the send results in a failure, or on
+/// MMRSError::ServerRejected if Mattermost returns a non-2xx status.
+/// This is a plain `async fn` and can be `.await`ed from inside an existing
+/// Tokio runtime (e.g. an Axum or Actix handler). Synchronous callers who
+/// don't have a runtime of their own should use [`send_message_blocking`].
+pub async fn send_message(uri: &str, body: String) -> Result<MMResponse, MMRSError> {
+    let response = reqwest::Client::new()
         .post(uri)
         .body(body)
         .send()
         .await
-        .map_err(MMRSError::HTTPRequestError)?
-        .status();
+        .map_err(classify_reqwest_error)?;
 
-    Ok(status_code)
+    into_mm_response(response).await
+}
+
+/// Synchronous wrapper around [`send_message`] for callers without a Tokio
+/// runtime of their own: spins up a throwaway runtime, awaits the send, and
+/// tears the runtime down. Panics if called from within an existing runtime.
+#[tokio::main]
+pub async fn send_message_blocking(uri: &str, body: String) -> Result<MMResponse, MMRSError> {
+    send_message(uri, body).await
 }
 
 #[cfg(test)]
@@ -113,9 +548,10 @@ mod tests {
             username: None,
             icon_url: None,
             icon_emoji: None,
-            attachments: None,
+            attachments: Vec::new(),
             r#type: None,
             props: None,
+            file_ids: Vec::new(),
         };
 
         let body = x.to_json().unwrap();
@@ -123,6 +559,38 @@ mod tests {
         assert_eq!(body, "{\"text\":\"Hello, world!\"}");
     }
 
+    #[test]
+    fn attachment_json_check() {
+        use crate as mmrs;
+
+        let mut attachment = mmrs::MMAttachment::new();
+        attachment.fallback = Some("fallback text".to_string());
+        attachment.fields.push(mmrs::MMField {
+            title: "Field".to_string(),
+            value: "Value".to_string(),
+            short: true,
+        });
+
+        let x: mmrs::MMBody = mmrs::MMBody {
+            text: None,
+            channel: None,
+            username: None,
+            icon_url: None,
+            icon_emoji: None,
+            attachments: vec![attachment],
+            r#type: None,
+            props: None,
+            file_ids: Vec::new(),
+        };
+
+        let body = x.to_json().unwrap();
+
+        assert_eq!(
+            body,
+            "{\"attachments\":[{\"fallback\":\"fallback text\",\"fields\":[{\"title\":\"Field\",\"value\":\"Value\",\"short\":true}]}]}"
+        );
+    }
+
     #[test]
     fn send_test() {
         use crate as mmrs;
@@ -140,16 +608,213 @@ mod tests {
             username: None,
             icon_url: None,
             icon_emoji: None,
-            attachments: None,
+            attachments: Vec::new(),
+            r#type: None,
+            props: None,
+            file_ids: Vec::new(),
+        };
+
+        let body = x.to_json().unwrap();
+
+        assert_eq!(
+            mmrs::send_message_blocking(&mockito::server_url(), body.to_string())
+                .unwrap()
+                .status,
+            reqwest::StatusCode::OK
+        );
+    }
+
+    #[tokio::test]
+    async fn send_message_async_test() {
+        use crate as mmrs;
+        use mockito::{mock, Matcher};
+
+        let _m = mock("POST", "/")
+            .match_body(
+                Matcher::JsonString("{\"text\":\"Hello, world!\"}".to_string())
+            )
+            .create();
+
+        let x: mmrs::MMBody = mmrs::MMBody {
+            text: Some("Hello, world!".to_string()),
+            channel: None,
+            username: None,
+            icon_url: None,
+            icon_emoji: None,
+            attachments: Vec::new(),
             r#type: None,
             props: None,
+            file_ids: Vec::new(),
         };
 
         let body = x.to_json().unwrap();
 
         assert_eq!(
-            mmrs::send_message(&mockito::server_url(), body.to_string()).unwrap(),
+            mmrs::send_message(&mockito::server_url(), body.to_string())
+                .await
+                .unwrap()
+                .status,
             reqwest::StatusCode::OK
         );
     }
+
+    #[tokio::test]
+    async fn client_send_test() {
+        use crate as mmrs;
+        use mockito::{mock, Matcher};
+
+        let _m = mock("POST", "/")
+            .match_body(
+                Matcher::JsonString("{\"text\":\"Hello, world!\"}".to_string())
+            )
+            .create();
+
+        let client = mmrs::MMClient::new(&mockito::server_url());
+
+        let mut x: mmrs::MMBody = mmrs::MMBody::new();
+        x.text = Some("Hello, world!".to_string());
+
+        assert_eq!(client.send(x).await.unwrap().status, reqwest::StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn client_send_server_rejected_test() {
+        use crate as mmrs;
+        use mockito::mock;
+
+        let _m = mock("POST", "/")
+            .with_status(404)
+            .with_body("{\"message\":\"Couldn't find the channel.\"}")
+            .create();
+
+        let client = mmrs::MMClient::new(&mockito::server_url());
+
+        match client.send(mmrs::MMBody::new()).await {
+            Err(mmrs::MMRSError::ServerRejected { status, body }) => {
+                assert_eq!(status, reqwest::StatusCode::NOT_FOUND);
+                assert_eq!(body, "{\"message\":\"Couldn't find the channel.\"}");
+            }
+            other => panic!("expected ServerRejected, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn client_retry_on_429_test() {
+        use crate as mmrs;
+        use mockito::mock;
+        use std::time::Duration;
+
+        let _rate_limited = mock("POST", "/")
+            .with_status(429)
+            .with_header("Retry-After", "0")
+            .expect(1)
+            .create();
+        let _ok = mock("POST", "/").with_status(200).create();
+
+        let client = mmrs::MMClient::builder(&mockito::server_url())
+            .retry(mmrs::RetryConfig::new(
+                2,
+                Duration::from_millis(1),
+                Duration::from_millis(10),
+            ))
+            .build();
+
+        let x: mmrs::MMBody = mmrs::MMBody::new();
+
+        assert_eq!(client.send(x).await.unwrap().status, reqwest::StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn client_invalid_webhook_url_test() {
+        use crate as mmrs;
+
+        let client = mmrs::MMClient::new("not a valid url");
+
+        match client.send(mmrs::MMBody::new()).await {
+            Err(mmrs::MMRSError::InvalidWebhookURL(_)) => {}
+            other => panic!("expected InvalidWebhookURL, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn classify_reqwest_error_timeout_test() {
+        use crate as mmrs;
+        use mockito::mock;
+        use std::time::Duration;
+
+        let _m = mock("POST", "/").with_status(200).create();
+
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_nanos(1))
+            .build()
+            .unwrap();
+
+        let err = client
+            .post(mockito::server_url())
+            .body("{}")
+            .send()
+            .await
+            .unwrap_err();
+
+        match mmrs::classify_reqwest_error(err) {
+            mmrs::MMRSError::Timeout(_) => {}
+            other => panic!("expected Timeout, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn error_source_test() {
+        use crate as mmrs;
+        use std::error::Error as _;
+
+        let bad_json = mmrs::MMRSError::BadJSONData(
+            serde_json::from_str::<serde_json::Value>("not json").unwrap_err(),
+        );
+        assert!(bad_json.source().is_some());
+
+        let rejected = mmrs::MMRSError::ServerRejected {
+            status: reqwest::StatusCode::NOT_FOUND,
+            body: "oops".to_string(),
+        };
+        assert!(rejected.source().is_none());
+    }
+
+    #[tokio::test]
+    async fn client_upload_file_test() {
+        use crate as mmrs;
+        use mockito::mock;
+
+        let _m = mock("POST", "/api/v4/files")
+            .with_status(201)
+            .with_header("content-type", "application/json")
+            .with_body("{\"file_infos\":[{\"id\":\"abc123\"}]}")
+            .create();
+
+        let client = mmrs::MMClient::builder(&mockito::server_url())
+            .api_base(&mockito::server_url())
+            .token("test-token")
+            .build();
+
+        let file_ids = client
+            .upload_file("channel123", "example.txt", b"file contents".to_vec())
+            .await
+            .unwrap();
+
+        assert_eq!(file_ids, vec!["abc123".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn client_upload_file_not_configured_test() {
+        use crate as mmrs;
+
+        let client = mmrs::MMClient::new(&mockito::server_url());
+
+        match client
+            .upload_file("channel123", "example.txt", b"file contents".to_vec())
+            .await
+        {
+            Err(mmrs::MMRSError::FilesAPINotConfigured) => {}
+            other => panic!("expected FilesAPINotConfigured, got {:?}", other),
+        }
+    }
 }